@@ -1,26 +1,110 @@
 mod data_cleaning {
-    use std::fs::File;
-    use std::io::{BufReader, BufRead};
+    /// One logical row of the rail CSV after column mapping. Only the fields the
+    /// analysis actually needs are pulled out; `weight` is optional and stays
+    /// `None` when no weight column is mapped or the value can't be parsed.
+    pub struct RailRecord {
+        pub from_node: String,
+        pub to_node: String,
+        pub county: String,
+        pub weight: Option<f64>,
+    }
 
-    pub fn parse_csv(file_path: &str) -> Vec<Vec<String>> {
-        let file = File::open(file_path).expect("Can't open file");
-        let reader = BufReader::new(file);
+    /// Maps each logical field to the CSV header it lives under, so the crate
+    /// works on datasets whose column order differs. `weight` is optional.
+    pub struct ColumnMapping {
+        pub from_node: String,
+        pub to_node: String,
+        pub county: String,
+        pub weight: Option<String>,
+    }
 
-        reader.lines().skip(1) // skips the column titles
-            .filter_map(|line| line.ok()) // gets rid of unreadable lines
-            .map(|line| line.split(',').map(|s| s.trim().to_string()).collect()) // splits by commas and collects
-            .collect()
+    /// RFC-4180-aware tokenizer: splits the raw file into rows of fields while
+    /// honouring quoted fields, commas and newlines embedded inside quotes, and
+    /// escaped `""` quotes. Returns every row including the header.
+    fn read_csv(content: &str) -> Vec<Vec<String>> {
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut record: Vec<String> = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut chars = content.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if in_quotes {
+                match c {
+                    '"' if chars.peek() == Some(&'"') => {
+                        chars.next(); // "" is a single escaped quote
+                        field.push('"');
+                    }
+                    '"' => in_quotes = false, // closing quote
+                    _ => field.push(c),
+                }
+            } else {
+                match c {
+                    '"' => in_quotes = true,
+                    ',' => record.push(std::mem::take(&mut field)),
+                    '\n' => {
+                        record.push(std::mem::take(&mut field));
+                        rows.push(std::mem::take(&mut record));
+                    }
+                    '\r' => {} // swallow so CRLF line endings collapse to LF
+                    _ => field.push(c),
+                }
+            }
+        }
+
+        // Flush a trailing record when the file doesn't end in a newline.
+        if !field.is_empty() || !record.is_empty() {
+            record.push(field);
+            rows.push(record);
+        }
+
+        rows
+    }
+
+    /// Parse the CSV at `file_path` into structured records, mapping logical
+    /// fields to columns by header name. Panics loudly if a required column is
+    /// missing rather than silently producing garbage from the wrong offset.
+    pub fn parse_records(file_path: &str, mapping: &ColumnMapping) -> Vec<RailRecord> {
+        let content = std::fs::read_to_string(file_path).expect("Can't open file");
+        let mut rows = read_csv(&content).into_iter();
+        let header = rows.next().expect("CSV has no header row");
+
+        let index_of = |name: &str| -> usize {
+            header
+                .iter()
+                .position(|h| h.trim() == name)
+                .unwrap_or_else(|| panic!("required column '{}' not found in CSV header", name))
+        };
+
+        let from_idx = index_of(&mapping.from_node);
+        let to_idx = index_of(&mapping.to_node);
+        let county_idx = index_of(&mapping.county);
+        let weight_idx = mapping.weight.as_ref().map(|name| index_of(name));
+
+        rows.map(|row| {
+            let field = |i: usize| row.get(i).map(|s| s.trim().to_string()).unwrap_or_default();
+            RailRecord {
+                from_node: field(from_idx),
+                to_node: field(to_idx),
+                county: field(county_idx),
+                weight: weight_idx
+                    .and_then(|i| row.get(i))
+                    .and_then(|value| value.trim().parse::<f64>().ok()),
+            }
+        })
+        .collect()
     }
 }
 
 mod adjacency_lists {
-    use std::collections::{HashMap, HashSet};
+    use std::collections::HashMap;
+    use crate::data_cleaning::RailRecord;
 
-    pub fn build_node_adjacency(data: &[Vec<String>]) -> HashMap<String, Vec<String>> {
+    pub fn build_node_adjacency(records: &[RailRecord]) -> HashMap<String, Vec<String>> {
         let mut node_adjacency: HashMap<String, Vec<String>> = HashMap::new();
-        for row in data {
-            let from_node = row[2].clone(); // FRFRANODE - from node
-            let to_node = row[3].clone(); // TOFRANODE - to node
+        for record in records {
+            let from_node = record.from_node.clone();
+            let to_node = record.to_node.clone();
 
             node_adjacency.entry(from_node.clone()).or_default().push(to_node.clone());
             node_adjacency.entry(to_node).or_default().push(from_node);
@@ -29,17 +113,15 @@ mod adjacency_lists {
         node_adjacency
     }
 
-    pub fn build_county_adjacency(data: &[Vec<String>], node_adjacency: &HashMap<String, Vec<String>>,
+    pub fn build_county_adjacency(records: &[RailRecord], node_adjacency: &HashMap<String, Vec<String>>,
     ) -> HashMap<String, Vec<String>> {
         let mut county_adjacency: HashMap<String, Vec<String>> = HashMap::new();
         let mut node_to_county: HashMap<String, String> = HashMap::new();
-        
+
         //initialized HashMaps for county and node_to_county adjacency lists
-        for row in data {
-            let node = row[2].clone(); // FRFRANODE - from node
-            let county = row[6].clone(); // STCNTYFIPS - state and county ID
-            if !county.is_empty() { // skips over rows where state/county ID is missing
-                node_to_county.insert(node, county);
+        for record in records {
+            if !record.county.is_empty() { // skips over rows where state/county ID is missing
+                node_to_county.insert(record.from_node.clone(), record.county.clone());
             }
         }
 
@@ -64,6 +146,70 @@ mod adjacency_lists {
         county_adjacency
     }
 
+    /// Like `build_node_adjacency`, but attaches an edge weight to every link.
+    /// The weight comes from each record's optional mileage; rows whose weight
+    /// is missing fall back to a unit weight so the weighted graph stays
+    /// connected wherever the plain graph is.
+    pub fn build_node_adjacency_weighted(
+        records: &[RailRecord],
+    ) -> HashMap<String, Vec<(String, f64)>> {
+        let mut node_adjacency: HashMap<String, Vec<(String, f64)>> = HashMap::new();
+        for record in records {
+            let from_node = record.from_node.clone();
+            let to_node = record.to_node.clone();
+            let weight = record.weight.unwrap_or(1.0); // default to one mile when mileage is absent
+
+            node_adjacency.entry(from_node.clone()).or_default().push((to_node.clone(), weight));
+            node_adjacency.entry(to_node).or_default().push((from_node, weight));
+        }
+
+        node_adjacency
+    }
+
+    /// Weighted counterpart to `build_county_adjacency`. Where several node
+    /// links join the same pair of counties we keep the shortest one, so the
+    /// stored weight is the best (minimum-mileage) rail connection between them.
+    pub fn build_county_adjacency_weighted(
+        records: &[RailRecord],
+        node_adjacency: &HashMap<String, Vec<(String, f64)>>,
+    ) -> HashMap<String, Vec<(String, f64)>> {
+        let mut node_to_county: HashMap<String, String> = HashMap::new();
+        for record in records {
+            if !record.county.is_empty() {
+                node_to_county.insert(record.from_node.clone(), record.county.clone());
+            }
+        }
+
+        // Collapse parallel county links down to the minimum weight per pair.
+        let mut best: HashMap<String, HashMap<String, f64>> = HashMap::new();
+        for (node, neighbors) in node_adjacency {
+            if let Some(county) = node_to_county.get(node) {
+                for (neighbor, weight) in neighbors {
+                    if let Some(neighbor_county) = node_to_county.get(neighbor) {
+                        if county != neighbor_county {
+                            let entry = best
+                                .entry(county.clone())
+                                .or_default()
+                                .entry(neighbor_county.clone())
+                                .or_insert(f64::INFINITY);
+                            if *weight < *entry {
+                                *entry = *weight;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        best.into_iter()
+            .map(|(county, neighbors)| {
+                let mut edges: Vec<(String, f64)> = neighbors.into_iter().collect();
+                edges.sort_by(|a, b| a.0.cmp(&b.0)); // keep neighbor order deterministic
+                (county, edges)
+            })
+            .collect()
+    }
+
     pub fn remove_county(county: &String, adjacency_list: &HashMap<String, Vec<String>>,
     ) -> HashMap<String, Vec<String>> {
         let mut new_adjacency_list = adjacency_list.clone();
@@ -76,7 +222,8 @@ mod adjacency_lists {
 }
 
 mod graph_analysis {
-    use std::collections::{HashMap, HashSet, VecDeque};
+    use std::cmp::Ordering;
+    use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
     use crate::adjacency_lists::remove_county;
 
     pub fn connectivity_analysis(adjacency_list: &HashMap<String, Vec<String>>) -> Vec<(String, usize)> {
@@ -134,23 +281,286 @@ mod graph_analysis {
         max_size
     }
 
-    /// Betweenness Centrality Analysis
-    pub fn betweenness_centrality(
+    /// Find all articulation points (cut vertices) in the county graph using
+    /// Tarjan's algorithm in a single DFS pass. A county is an articulation
+    /// point if removing it (and its rail links) disconnects the network.
+    /// Each critical county is paired with a criticality score: the number of
+    /// DFS subtrees it is the sole connector for (for the DFS root, its number
+    /// of children). Results are sorted descending so the counties whose loss
+    /// fragments the network the most come first. It finds the cut counties
+    /// without the expensive remove-and-recompute loop that `removal_impact`
+    /// performs.
+    pub fn articulation_points(
         adjacency_list: &HashMap<String, Vec<String>>,
     ) -> Vec<(String, usize)> {
-        let mut results = Vec::new();
+        let mut disc: HashMap<String, usize> = HashMap::new(); // discovery time
+        let mut low: HashMap<String, usize> = HashMap::new(); // low-link value
+        let mut cut_count: HashMap<String, usize> = HashMap::new(); // qualifying children
+        let mut timer = 0;
+
+        // County graphs can be deep, so we walk with an explicit stack instead
+        // of recursion to avoid overflowing the call stack. Each frame holds the
+        // node, the parent we reached it from, and the index of the next
+        // neighbor to examine so we can resume exactly where we paused.
+        for root in adjacency_list.keys() {
+            if disc.contains_key(root) {
+                continue; // already covered by an earlier component's DFS
+            }
+            let mut stack: Vec<(String, Option<String>, usize)> =
+                vec![(root.clone(), None, 0)];
+            let mut root_children = 0;
+
+            while let Some((node, parent, mut next)) = stack.pop() {
+                if next == 0 {
+                    // First visit: stamp discovery time and seed the low value.
+                    timer += 1;
+                    disc.insert(node.clone(), timer);
+                    low.insert(node.clone(), timer);
+                }
+
+                let mut descended = false;
+                if let Some(neighbors) = adjacency_list.get(&node) {
+                    while next < neighbors.len() {
+                        let child = &neighbors[next];
+                        next += 1;
+                        if Some(child) == parent.as_ref() {
+                            continue; // never climb back up the edge we arrived on
+                        }
+                        if let Some(&child_disc) = disc.get(child) {
+                            // Back edge: tighten this node's low value.
+                            let cur = low[&node];
+                            low.insert(node.clone(), cur.min(child_disc));
+                        } else {
+                            // Tree edge: descend, resuming this frame afterwards.
+                            if parent.is_none() {
+                                root_children += 1;
+                            }
+                            stack.push((node.clone(), parent.clone(), next));
+                            stack.push((child.clone(), Some(node.clone()), 0));
+                            descended = true;
+                            break;
+                        }
+                    }
+                }
+
+                if descended {
+                    continue; // handle the child before finishing this node
+                }
 
-        for county in adjacency_list.keys() {
-            let modified_adjacency = remove_county(county, adjacency_list);
-            let largest_component = largest_connected_component(&modified_adjacency);
+                // Node finished: fold its low value into the parent and decide
+                // whether the parent is a cut vertex on account of this subtree.
+                if let Some(parent) = parent {
+                    let child_low = low[&node];
+                    let parent_low = low[&parent];
+                    low.insert(parent.clone(), parent_low.min(child_low));
+                    if child_low >= disc[&parent] {
+                        *cut_count.entry(parent.clone()).or_insert(0) += 1;
+                    }
+                }
+            }
 
-            results.push((county.clone(), largest_component));
+            // The root counts only when it has more than one DFS child; the
+            // parent-side bookkeeping above over-counts it, so correct it here.
+            if root_children > 1 {
+                cut_count.insert(root.clone(), root_children);
+            } else {
+                cut_count.remove(root);
+            }
         }
 
+        let mut results: Vec<(String, usize)> = cut_count.into_iter().collect();
+        results.sort_by_key(|x| std::cmp::Reverse(x.1)); // most critical first
+        results
+    }
+
+    /// Removal-impact sweep: for every county, the size of the largest
+    /// connected component that survives once that county is removed. (This is
+    /// the largest-component-after-removal metric — *not* betweenness, which now
+    /// lives in `brandes_betweenness` — so it is named for what it computes.)
+    pub fn removal_impact(
+        adjacency_list: &HashMap<String, Vec<String>>,
+    ) -> Vec<(String, usize)> {
+        // Each county's removal is independent — `remove_county` returns its own
+        // owned graph, so the bodies are side-effect free. The request asked for
+        // rayon's `par_iter` here, but the crate has no `Cargo.toml` to declare
+        // that dependency, so we fan the sweep across a handful of scoped threads
+        // instead (std only) — same embarrassingly-parallel speedup, no new crate.
+        let counties: Vec<String> = adjacency_list.keys().cloned().collect();
+        let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        let chunk_size = counties.len().div_ceil(threads).max(1);
+
+        let mut results: Vec<(String, usize)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = counties
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(|| {
+                        chunk
+                            .iter()
+                            .map(|county| {
+                                let modified_adjacency = remove_county(county, adjacency_list);
+                                (county.clone(), largest_connected_component(&modified_adjacency))
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+        });
+
         results.sort_by(|a, b| a.1.cmp(&b.1)); // Sort by impact (ascending)
         results
     }
 
+    /// Brandes' algorithm for true betweenness centrality on the unweighted
+    /// county graph: for each county, the fraction of shortest paths between
+    /// every other pair of counties that run through it, summed over all pairs
+    /// (not normalised by the number of pairs). Far more informative than raw degree for spotting which
+    /// counties carry the most shortest-path rail traffic. Returns every county
+    /// with its score, sorted descending.
+    pub fn brandes_betweenness(
+        adjacency_list: &HashMap<String, Vec<String>>,
+    ) -> Vec<(String, f64)> {
+        let mut centrality: HashMap<String, f64> =
+            adjacency_list.keys().map(|k| (k.clone(), 0.0)).collect();
+
+        for source in adjacency_list.keys() {
+            let mut stack: Vec<String> = Vec::new(); // vertices in BFS order
+            let mut predecessors: HashMap<String, Vec<String>> =
+                adjacency_list.keys().map(|k| (k.clone(), Vec::new())).collect();
+            let mut sigma: HashMap<String, f64> =
+                adjacency_list.keys().map(|k| (k.clone(), 0.0)).collect();
+            let mut distance: HashMap<String, i64> =
+                adjacency_list.keys().map(|k| (k.clone(), -1)).collect();
+
+            sigma.insert(source.clone(), 1.0);
+            distance.insert(source.clone(), 0);
+            let mut queue: VecDeque<String> = VecDeque::new();
+            queue.push_back(source.clone());
+
+            // BFS that also counts shortest paths and records predecessors.
+            while let Some(v) = queue.pop_front() {
+                stack.push(v.clone());
+                if let Some(neighbors) = adjacency_list.get(&v) {
+                    for w in neighbors {
+                        if !distance.contains_key(w) {
+                            continue; // neighbor outside the keyed graph, skip it
+                        }
+                        if distance[w] < 0 {
+                            // First time we reach w: record its level, enqueue it.
+                            distance.insert(w.clone(), distance[&v] + 1);
+                            queue.push_back(w.clone());
+                        }
+                        if distance[w] == distance[&v] + 1 {
+                            // Another shortest path to w, arriving through v.
+                            let added = sigma[&v];
+                            *sigma.get_mut(w).unwrap() += added;
+                            predecessors.get_mut(w).unwrap().push(v.clone());
+                        }
+                    }
+                }
+            }
+
+            // Accumulate dependencies back-to-front in reverse BFS order.
+            let mut delta: HashMap<String, f64> =
+                adjacency_list.keys().map(|k| (k.clone(), 0.0)).collect();
+            while let Some(w) = stack.pop() {
+                let coeff = (1.0 + delta[&w]) / sigma[&w];
+                for v in predecessors[&w].clone() {
+                    let contribution = sigma[&v] * coeff;
+                    *delta.get_mut(&v).unwrap() += contribution;
+                }
+                if &w != source {
+                    *centrality.get_mut(&w).unwrap() += delta[&w];
+                }
+            }
+        }
+
+        // Undirected graph: every shortest path is walked from both ends.
+        for value in centrality.values_mut() {
+            *value /= 2.0;
+        }
+
+        let mut results: Vec<(String, f64)> = centrality.into_iter().collect();
+        results.sort_by(|a, b| b.1.total_cmp(&a.1)); // most central first
+        results
+    }
+
+    /// Min-heap entry for Dijkstra. `BinaryHeap` is a max-heap, so we flip the
+    /// comparison on `distance` to make the closest county pop first. `f64` has
+    /// no total order of its own, so we lean on `total_cmp`.
+    struct State {
+        distance: f64,
+        county: String,
+    }
+
+    impl PartialEq for State {
+        fn eq(&self, other: &Self) -> bool {
+            self.distance == other.distance
+        }
+    }
+    impl Eq for State {}
+    impl Ord for State {
+        fn cmp(&self, other: &Self) -> Ordering {
+            other.distance.total_cmp(&self.distance) // reversed for a min-heap
+        }
+    }
+    impl PartialOrd for State {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    /// Dijkstra's shortest-path search over a weighted county graph, using a
+    /// binary-heap priority queue. Returns the shortest weighted distance from
+    /// `start` to every county reachable from it.
+    pub fn dijkstra(
+        adjacency_list: &HashMap<String, Vec<(String, f64)>>,
+        start: &str,
+    ) -> HashMap<String, f64> {
+        let mut distances: HashMap<String, f64> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+
+        distances.insert(start.to_string(), 0.0);
+        heap.push(State { distance: 0.0, county: start.to_string() });
+
+        while let Some(State { distance, county }) = heap.pop() {
+            if distance > *distances.get(&county).unwrap_or(&f64::INFINITY) {
+                continue; // stale entry, we already found a shorter route
+            }
+            if let Some(neighbors) = adjacency_list.get(&county) {
+                for (neighbor, weight) in neighbors {
+                    let next = distance + weight;
+                    if next < *distances.get(neighbor).unwrap_or(&f64::INFINITY) {
+                        distances.insert(neighbor.clone(), next);
+                        heap.push(State { distance: next, county: neighbor.clone() });
+                    }
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Weighted Average Shortest Path Length: the mean true rail distance over
+    /// every ordered pair of reachable counties, running `dijkstra` from each
+    /// source. Reflects actual travel distance instead of hop count.
+    pub fn weighted_aspl(adjacency_list: &HashMap<String, Vec<(String, f64)>>) -> f64 {
+        let mut total_distance = 0.0;
+        let mut path_count = 0;
+
+        for start in adjacency_list.keys() {
+            let distances = dijkstra(adjacency_list, start);
+            for (county, distance) in &distances {
+                if county != start {
+                    total_distance += distance;
+                    path_count += 1;
+                }
+            }
+        }
+
+        total_distance / path_count as f64
+    }
+
     /// Compute the Average Shortest Path Length (ASPL) for the graph.
     pub fn calculate_aspl(adjacency_list: &HashMap<String, Vec<String>>) -> f64 {
         let mut total_length = 0;
@@ -198,13 +608,271 @@ mod graph_analysis {
     }
 }
 
+mod graph_diff {
+    use std::collections::{HashMap, HashSet};
+
+    /// A structured comparison of two county adjacency snapshots, e.g. two data
+    /// releases. Lets analysts quantify connectivity gained or lost instead of
+    /// eyeballing two separate runs.
+    pub struct GraphDiff {
+        pub added_nodes: Vec<String>,
+        pub removed_nodes: Vec<String>,
+        pub added_edges: Vec<(String, String)>,
+        pub removed_edges: Vec<(String, String)>,
+        pub changed: Vec<NodeChange>,
+    }
+
+    /// A county whose neighbor list shifted between snapshots. `node` is the
+    /// county id, or `"old -> new"` when a greedy rename match was inferred.
+    /// `score` is the edit distance over the two sorted neighbor lists.
+    pub struct NodeChange {
+        pub node: String,
+        pub score: usize,
+    }
+
+    /// Edit distance (insert / delete / substitute, each cost 1) between two
+    /// sequences, used over sorted neighbor lists to measure how much a
+    /// county's connectivity changed.
+    fn levenshtein(a: &[String], b: &[String]) -> usize {
+        let mut prev: Vec<usize> = (0..=b.len()).collect();
+        let mut curr = vec![0; b.len() + 1];
+        for (i, ai) in a.iter().enumerate() {
+            curr[0] = i + 1;
+            for (j, bj) in b.iter().enumerate() {
+                let cost = if ai == bj { 0 } else { 1 };
+                curr[j + 1] = (prev[j + 1] + 1) // deletion
+                    .min(curr[j] + 1) // insertion
+                    .min(prev[j] + cost); // substitution
+            }
+            std::mem::swap(&mut prev, &mut curr);
+        }
+        prev[b.len()]
+    }
+
+    /// Neighbor list sorted into a canonical order for comparison.
+    fn sorted(neighbors: &[String]) -> Vec<String> {
+        let mut values = neighbors.to_vec();
+        values.sort();
+        values
+    }
+
+    /// Normalize a graph into a set of undirected edges, each stored as a single
+    /// sorted pair so (a, b) and (b, a) collapse together.
+    fn edge_set(graph: &HashMap<String, Vec<String>>) -> HashSet<(String, String)> {
+        let mut edges = HashSet::new();
+        for (node, neighbors) in graph {
+            for neighbor in neighbors {
+                let edge = if node <= neighbor {
+                    (node.clone(), neighbor.clone())
+                } else {
+                    (neighbor.clone(), node.clone())
+                };
+                edges.insert(edge);
+            }
+        }
+        edges
+    }
+
+    /// Jaccard similarity of two neighbor sets: shared neighbors over total.
+    fn similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+        let union = a.union(b).count();
+        if union == 0 {
+            0.0
+        } else {
+            a.intersection(b).count() as f64 / union as f64
+        }
+    }
+
+    /// Compare two county adjacency snapshots and report the difference.
+    pub fn diff(
+        old: &HashMap<String, Vec<String>>,
+        new: &HashMap<String, Vec<String>>,
+    ) -> GraphDiff {
+        let old_keys: HashSet<&String> = old.keys().collect();
+        let new_keys: HashSet<&String> = new.keys().collect();
+
+        let mut added_nodes: Vec<String> =
+            new_keys.difference(&old_keys).map(|n| (*n).clone()).collect();
+        let mut removed_nodes: Vec<String> =
+            old_keys.difference(&new_keys).map(|n| (*n).clone()).collect();
+
+        let old_edges = edge_set(old);
+        let new_edges = edge_set(new);
+        let mut added_edges: Vec<(String, String)> =
+            new_edges.difference(&old_edges).cloned().collect();
+        let mut removed_edges: Vec<(String, String)> =
+            old_edges.difference(&new_edges).cloned().collect();
+
+        // Counties present in both snapshots: report any whose neighbors shifted.
+        let mut changed: Vec<NodeChange> = Vec::new();
+        for node in old_keys.intersection(&new_keys) {
+            let score = levenshtein(&sorted(&old[*node]), &sorted(&new[*node]));
+            if score > 0 {
+                changed.push(NodeChange { node: (*node).clone(), score });
+            }
+        }
+
+        // Greedily pair a removed county with the added county it most overlaps:
+        // a high neighbor-set similarity suggests the same county, relabelled.
+        let old_sets: HashMap<String, HashSet<String>> = removed_nodes
+            .iter()
+            .map(|n| (n.clone(), old[n].iter().cloned().collect()))
+            .collect();
+        let new_sets: HashMap<String, HashSet<String>> = added_nodes
+            .iter()
+            .map(|n| (n.clone(), new[n].iter().cloned().collect()))
+            .collect();
+
+        let mut matched_old: HashSet<String> = HashSet::new();
+        let mut matched_new: HashSet<String> = HashSet::new();
+        for removed in &removed_nodes {
+            let mut best: Option<(String, f64)> = None;
+            for added in &added_nodes {
+                if matched_new.contains(added) {
+                    continue;
+                }
+                let sim = similarity(&old_sets[removed], &new_sets[added]);
+                if sim > 0.5 && best.as_ref().is_none_or(|(_, b)| sim > *b) {
+                    best = Some((added.clone(), sim));
+                }
+            }
+            if let Some((added, _)) = best {
+                let score = levenshtein(&sorted(&old[removed]), &sorted(&new[&added]));
+                changed.push(NodeChange { node: format!("{} -> {}", removed, added), score });
+                matched_old.insert(removed.clone());
+                matched_new.insert(added);
+            }
+        }
+        added_nodes.retain(|n| !matched_new.contains(n));
+        removed_nodes.retain(|n| !matched_old.contains(n));
+
+        // Sort everything for deterministic, readable output.
+        added_nodes.sort();
+        removed_nodes.sort();
+        added_edges.sort();
+        removed_edges.sort();
+        changed.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.node.cmp(&b.node)));
+
+        GraphDiff { added_nodes, removed_nodes, added_edges, removed_edges, changed }
+    }
+}
+
+mod connectivity {
+    use std::collections::HashMap;
+
+    /// Disjoint-set (union-find) over county ids, with path compression on
+    /// `find` and union-by-size on `join`. Built once over every edge, it
+    /// answers same-component queries in near O(α(n)) time, replacing repeated
+    /// BFS sweeps from `largest_connected_component`.
+    pub struct UnionFind {
+        parent: HashMap<String, String>,
+        size: HashMap<String, usize>,
+    }
+
+    impl UnionFind {
+        /// Build the structure in a single pass over the adjacency list, unioning
+        /// each county with every neighbor.
+        pub fn from_adjacency(adjacency_list: &HashMap<String, Vec<String>>) -> Self {
+            let mut uf = UnionFind { parent: HashMap::new(), size: HashMap::new() };
+            for (node, neighbors) in adjacency_list {
+                uf.add(node);
+                for neighbor in neighbors {
+                    uf.add(neighbor);
+                    uf.join(node, neighbor);
+                }
+            }
+            uf
+        }
+
+        /// Register a county as its own singleton set the first time it is seen.
+        fn add(&mut self, node: &str) {
+            if !self.parent.contains_key(node) {
+                self.parent.insert(node.to_string(), node.to_string());
+                self.size.insert(node.to_string(), 1);
+            }
+        }
+
+        /// Representative of `node`'s set, compressing the path to the root so
+        /// later lookups are faster.
+        pub fn find(&mut self, node: &str) -> String {
+            let mut root = node.to_string();
+            while self.parent[&root] != root {
+                root = self.parent[&root].clone();
+            }
+            // Second pass: point every node along the path straight at the root.
+            let mut current = node.to_string();
+            while current != root {
+                let next = self.parent[&current].clone();
+                self.parent.insert(current, root.clone());
+                current = next;
+            }
+            root
+        }
+
+        /// Merge the sets containing `a` and `b`, attaching the smaller tree
+        /// under the larger (union by size).
+        pub fn join(&mut self, a: &str, b: &str) {
+            let root_a = self.find(a);
+            let root_b = self.find(b);
+            if root_a == root_b {
+                return;
+            }
+            let (big, small) = if self.size[&root_a] >= self.size[&root_b] {
+                (root_a, root_b)
+            } else {
+                (root_b, root_a)
+            };
+            self.parent.insert(small.clone(), big.clone());
+            let moved = self.size[&small];
+            *self.size.get_mut(&big).unwrap() += moved;
+        }
+
+        /// Whether two counties sit in the same connected component. Unknown
+        /// counties are never connected.
+        pub fn connected(&mut self, a: &str, b: &str) -> bool {
+            if !self.parent.contains_key(a) || !self.parent.contains_key(b) {
+                return false;
+            }
+            self.find(a) == self.find(b)
+        }
+
+        /// Representative id of the component containing `county`, or `None` when
+        /// the county is unknown.
+        pub fn component_of(&mut self, county: &str) -> Option<String> {
+            if self.parent.contains_key(county) {
+                Some(self.find(county))
+            } else {
+                None
+            }
+        }
+
+        /// Census of component sizes keyed by representative id, in one pass over
+        /// all counties.
+        pub fn component_sizes(&mut self) -> HashMap<String, usize> {
+            let nodes: Vec<String> = self.parent.keys().cloned().collect();
+            let mut sizes: HashMap<String, usize> = HashMap::new();
+            for node in nodes {
+                let root = self.find(&node);
+                *sizes.entry(root).or_insert(0) += 1;
+            }
+            sizes
+        }
+    }
+}
+
 fn main() {
     let file_path = "Passenger_rail_data.csv";
 
-    // Parse the CSV and get the adjacency list for counties
-    let data = data_cleaning::parse_csv(file_path);
-    let node_adjacency = adjacency_lists::build_node_adjacency(&data);
-    let county_adjacency = adjacency_lists::build_county_adjacency(&data, &node_adjacency);
+    // Map the FRA columns by header name, then parse the CSV into records
+    let mapping = data_cleaning::ColumnMapping {
+        from_node: "FRFRANODE".to_string(), // from node
+        to_node: "TOFRANODE".to_string(),   // to node
+        county: "STCNTYFIPS".to_string(),   // state and county ID
+        weight: Some("MILES".to_string()),  // per-segment mileage
+    };
+    let records = data_cleaning::parse_records(file_path, &mapping);
+    let node_adjacency = adjacency_lists::build_node_adjacency(&records);
+    let county_adjacency = adjacency_lists::build_county_adjacency(&records, &node_adjacency);
 
     // Step 1: Top 20 Most Connected Counties by Degree using connectivity_analysis function
     println!("Top 20 Most Connected Counties by Degree:");
@@ -213,9 +881,19 @@ fn main() {
         println!("County: {}, Degree: {}", county, degree);
     }
 
-    // Step 2: Top 20 Counties by Smallest Largest Connected Component Size (using largest_connected_component)
+    // Step 2: Top 20 Critical Counties (articulation points) whose removal splits the rail network
+    println!("\nTop 20 Critical Counties (articulation points) whose removal disconnects the network:");
+    let critical = graph_analysis::articulation_points(&county_adjacency);
+    for (county, criticality) in critical.iter().take(20) {
+        println!(
+            "County: {}, Criticality (subtrees it solely connects): {}",
+            county, criticality
+        );
+    }
+
+    // Step 2b: Top 20 Counties by Smallest Largest Connected Component Size after removal
     println!("\nTop 20 Counties by Smallest Largest Connected Component Size after removal:");
-    let component_sizes = graph_analysis::betweenness_centrality(&county_adjacency);
+    let component_sizes = graph_analysis::removal_impact(&county_adjacency);
     for (county, component_size) in component_sizes.iter().take(20) {
         println!(
             "County: {}, Largest Component Size After Removal: {}",
@@ -233,48 +911,131 @@ fn main() {
         "ASPL after removing county {}: {:.3}",
         county_to_analyze, aspl_for_county
     );
+
+    // Step 4: Weighted ASPL using real per-segment mileage instead of hop count
+    let weighted_nodes = adjacency_lists::build_node_adjacency_weighted(&records);
+    let weighted_counties =
+        adjacency_lists::build_county_adjacency_weighted(&records, &weighted_nodes);
+    let weighted_aspl = graph_analysis::weighted_aspl(&weighted_counties);
+    println!(
+        "\nWeighted ASPL (mean shortest rail distance between counties): {:.3}",
+        weighted_aspl
+    );
+
+    // Step 5: Top 20 Counties by Betweenness Centrality (shortest-path traffic)
+    println!("\nTop 20 Counties by Betweenness Centrality:");
+    let betweenness = graph_analysis::brandes_betweenness(&county_adjacency);
+    for (county, score) in betweenness.iter().take(20) {
+        println!("County: {}, Betweenness: {:.3}", county, score);
+    }
+
+    // Step 6: Quantify the connectivity lost when the most critical county goes.
+    // We treat the post-removal network as a second "snapshot" and diff it.
+    if let Some((top_county, _)) = critical.first() {
+        let after = adjacency_lists::remove_county(top_county, &county_adjacency);
+        let diff = graph_diff::diff(&county_adjacency, &after);
+        println!(
+            "\nRemoving critical county {}: {} counties dropped, {} edges removed, {} edges added, {} counties with changed neighbors.",
+            top_county,
+            diff.removed_nodes.len(),
+            diff.removed_edges.len(),
+            diff.added_edges.len(),
+            diff.changed.len()
+        );
+        for change in diff.changed.iter().take(5) {
+            println!("  {} (change score {})", change.node, change.score);
+        }
+        for node in diff.added_nodes.iter().take(5) {
+            println!("  added county {}", node);
+        }
+    }
+
+    // Step 7: Connected-component census via union-find, built in one pass over
+    // the edges instead of repeatedly running BFS from scratch.
+    let mut components = connectivity::UnionFind::from_adjacency(&county_adjacency);
+    let sizes = components.component_sizes();
+    println!(
+        "\nThe rail network has {} connected components; the largest holds {} counties.",
+        sizes.len(),
+        sizes.values().max().copied().unwrap_or(0)
+    );
+    let (a, b) = ("08001", "08005");
+    println!(
+        "Counties {} and {} connected: {} (component of {}: {:?})",
+        a,
+        b,
+        components.connected(a, b),
+        a,
+        components.component_of(a)
+    );
 }
 #[cfg(test)]
 mod tests {
-    use super::*;
     use crate::data_cleaning;
+    use crate::data_cleaning::{ColumnMapping, RailRecord};
     use crate::adjacency_lists;
     use crate::graph_analysis;
+    use crate::graph_diff;
+    use crate::connectivity;
     use std::collections::HashMap;
-    use std::collections::HashSet;
 
     #[test]
-    fn test_parse_csv() {
-        let data = "FRFRANODE,TOFRANODE,STCNTYFIPS\nnode1,node2,10001\nnode2,node3,10002\n";
+    fn test_parse_records() {
+        // The NAME column carries a quoted comma, which the RFC-4180 reader must
+        // not mistake for a field separator, and the columns are out of order.
+        let data = "TOFRANODE,FRFRANODE,NAME,STCNTYFIPS\nnode2,node1,\"Adams, CO\",10001\nnode3,node2,Denver,10002\n";
         let file_path = "test.csv";
         std::fs::write(file_path, data).expect("Unable to write test file");
 
-        let rows = data_cleaning::parse_csv(file_path);
-        assert_eq!(rows.len(), 2); // Two rows after skipping header
-        assert_eq!(rows[0][2], "10001");
-        assert_eq!(rows[1][2], "10002");
+        let mapping = ColumnMapping {
+            from_node: "FRFRANODE".to_string(),
+            to_node: "TOFRANODE".to_string(),
+            county: "STCNTYFIPS".to_string(),
+            weight: None,
+        };
+        let records = data_cleaning::parse_records(file_path, &mapping);
+        assert_eq!(records.len(), 2); // Two rows after the header
+        assert_eq!(records[0].from_node, "node1");
+        assert_eq!(records[0].county, "10001");
+        assert_eq!(records[1].county, "10002");
+    }
+
+    #[test]
+    #[should_panic(expected = "required column")]
+    fn test_parse_records_missing_column() {
+        let data = "FRFRANODE,TOFRANODE\nnode1,node2\n";
+        let file_path = "test_missing.csv";
+        std::fs::write(file_path, data).expect("Unable to write test file");
+
+        let mapping = ColumnMapping {
+            from_node: "FRFRANODE".to_string(),
+            to_node: "TOFRANODE".to_string(),
+            county: "STCNTYFIPS".to_string(), // absent from the header
+            weight: None,
+        };
+        let _ = data_cleaning::parse_records(file_path, &mapping);
     }
 
     #[test]
     fn test_build_node_adjacency() {
-        let data = vec![
-            vec!["".into(), "".into(), "node1".into(), "node2".into()],
-            vec!["".into(), "".into(), "node2".into(), "node3".into()],
+        let records = vec![
+            RailRecord { from_node: "node1".into(), to_node: "node2".into(), county: "".into(), weight: None },
+            RailRecord { from_node: "node2".into(), to_node: "node3".into(), county: "".into(), weight: None },
         ];
 
-        let adjacency = adjacency_lists::build_node_adjacency(&data);
+        let adjacency = adjacency_lists::build_node_adjacency(&records);
         assert!(adjacency.contains_key("node2"));
         assert_eq!(adjacency["node2"].len(), 2); // node2 connects to node1 and node3
     }
 
     #[test]
     fn test_build_county_adjacency() {
-        let data = vec![
-            vec!["".into(), "".into(), "node1".into(), "node2".into(), "".into(), "".into(), "10001".into()],
-            vec!["".into(), "".into(), "node2".into(), "node3".into(), "".into(), "".into(), "10002".into()],
+        let records = vec![
+            RailRecord { from_node: "node1".into(), to_node: "node2".into(), county: "10001".into(), weight: None },
+            RailRecord { from_node: "node2".into(), to_node: "node3".into(), county: "10002".into(), weight: None },
         ];
-        let node_adjacency = adjacency_lists::build_node_adjacency(&data);
-        let county_adjacency = adjacency_lists::build_county_adjacency(&data, &node_adjacency);
+        let node_adjacency = adjacency_lists::build_node_adjacency(&records);
+        let county_adjacency = adjacency_lists::build_county_adjacency(&records, &node_adjacency);
 
         assert!(county_adjacency.contains_key("10001"));
         assert_eq!(county_adjacency["10001"].len(), 1);
@@ -317,6 +1078,31 @@ mod tests {
         assert_eq!(modified["10003"].len(), 0);
     }
 
+    #[test]
+    fn test_articulation_points() {
+        // Path graph A - B - C: only B is a cut vertex, regardless of DFS root.
+        let mut adjacency = HashMap::new();
+        adjacency.insert("A".to_string(), vec!["B".to_string()]);
+        adjacency.insert("B".to_string(), vec!["A".to_string(), "C".to_string()]);
+        adjacency.insert("C".to_string(), vec!["B".to_string()]);
+
+        let points = graph_analysis::articulation_points(&adjacency);
+        let names: Vec<String> = points.iter().map(|(county, _)| county.clone()).collect();
+        assert_eq!(names, vec!["B".to_string()]);
+    }
+
+    #[test]
+    fn test_articulation_points_cycle() {
+        // A triangle has no cut vertices: removing any node keeps the rest linked.
+        let mut adjacency = HashMap::new();
+        adjacency.insert("A".to_string(), vec!["B".to_string(), "C".to_string()]);
+        adjacency.insert("B".to_string(), vec!["A".to_string(), "C".to_string()]);
+        adjacency.insert("C".to_string(), vec!["A".to_string(), "B".to_string()]);
+
+        let points = graph_analysis::articulation_points(&adjacency);
+        assert!(points.is_empty());
+    }
+
     #[test]
     fn test_calculate_aspl() {
         let mut adjacency = HashMap::new();
@@ -328,6 +1114,30 @@ mod tests {
         assert!((aspl - 1.333).abs() < 0.001); // ASPL should match expected value
     }
 
+    #[test]
+    fn test_dijkstra() {
+        // A--1--B--2--C with a direct A--4--C edge: the cheapest A->C is via B.
+        let mut adjacency = HashMap::new();
+        adjacency.insert("A".to_string(), vec![("B".to_string(), 1.0), ("C".to_string(), 4.0)]);
+        adjacency.insert("B".to_string(), vec![("A".to_string(), 1.0), ("C".to_string(), 2.0)]);
+        adjacency.insert("C".to_string(), vec![("A".to_string(), 4.0), ("B".to_string(), 2.0)]);
+
+        let distances = graph_analysis::dijkstra(&adjacency, "A");
+        assert_eq!(distances["B"], 1.0);
+        assert_eq!(distances["C"], 3.0); // A -> B -> C beats the direct 4.0 edge
+    }
+
+    #[test]
+    fn test_build_node_adjacency_weighted() {
+        let records = vec![
+            RailRecord { from_node: "node1".into(), to_node: "node2".into(), county: "".into(), weight: Some(3.5) },
+        ];
+
+        let adjacency = adjacency_lists::build_node_adjacency_weighted(&records);
+        assert_eq!(adjacency["node1"][0], ("node2".to_string(), 3.5));
+        assert_eq!(adjacency["node2"][0], ("node1".to_string(), 3.5));
+    }
+
     #[test]
     fn test_calculate_aspl_with_removal() {
         let mut adjacency = HashMap::new();
@@ -340,21 +1150,85 @@ mod tests {
     }
 
     #[test]
-    fn test_betweenness_centrality() {
+    fn test_brandes_betweenness() {
+        // Path A - B - C: B sits on the only shortest path between A and C.
+        let mut adjacency = HashMap::new();
+        adjacency.insert("A".to_string(), vec!["B".to_string()]);
+        adjacency.insert("B".to_string(), vec!["A".to_string(), "C".to_string()]);
+        adjacency.insert("C".to_string(), vec!["B".to_string()]);
+
+        let centrality = graph_analysis::brandes_betweenness(&adjacency);
+        assert_eq!(centrality[0].0, "B"); // B carries the most traffic
+        assert!((centrality[0].1 - 1.0).abs() < 1e-9);
+        for (county, score) in &centrality[1..] {
+            assert!(score.abs() < 1e-9, "{} should have zero betweenness", county);
+        }
+    }
+
+    #[test]
+    fn test_union_find() {
+        // Two separate components: A-B and C-D.
+        let mut adjacency = HashMap::new();
+        adjacency.insert("A".to_string(), vec!["B".to_string()]);
+        adjacency.insert("B".to_string(), vec!["A".to_string()]);
+        adjacency.insert("C".to_string(), vec!["D".to_string()]);
+        adjacency.insert("D".to_string(), vec!["C".to_string()]);
+
+        let mut uf = connectivity::UnionFind::from_adjacency(&adjacency);
+        assert!(uf.connected("A", "B"));
+        assert!(!uf.connected("A", "C"));
+        assert_eq!(uf.component_of("A"), uf.component_of("B"));
+        assert!(!uf.connected("A", "Z")); // unknown county
+
+        let sizes = uf.component_sizes();
+        let mut counts: Vec<usize> = sizes.values().cloned().collect();
+        counts.sort();
+        assert_eq!(counts, vec![2, 2]); // two components of size two
+    }
+
+    #[test]
+    fn test_graph_diff() {
+        // old: A-B, B-C.  new: drops C, adds D linked to B.
+        let mut old = HashMap::new();
+        old.insert("A".to_string(), vec!["B".to_string()]);
+        old.insert("B".to_string(), vec!["A".to_string(), "C".to_string()]);
+        old.insert("C".to_string(), vec!["B".to_string()]);
+
+        let mut new = HashMap::new();
+        new.insert("A".to_string(), vec!["B".to_string()]);
+        new.insert("B".to_string(), vec!["A".to_string(), "D".to_string()]);
+        new.insert("D".to_string(), vec!["B".to_string()]);
+
+        let diff = graph_diff::diff(&old, &new);
+        // C (neighbors {B}) and D (neighbors {B}) overlap fully, so they are
+        // matched as a rename rather than a separate add/remove.
+        assert!(diff.added_nodes.is_empty());
+        assert!(diff.removed_nodes.is_empty());
+        assert_eq!(diff.added_edges, vec![("B".to_string(), "D".to_string())]);
+        assert_eq!(diff.removed_edges, vec![("B".to_string(), "C".to_string())]);
+        assert!(diff.changed.iter().any(|c| c.node == "C -> D"));
+    }
+
+    #[test]
+    fn test_removal_impact() {
         let mut adjacency = HashMap::new();
         adjacency.insert("A".to_string(), vec!["B".to_string(), "C".to_string()]);
         adjacency.insert("B".to_string(), vec!["A".to_string(), "C".to_string()]);
         adjacency.insert("C".to_string(), vec!["A".to_string(), "B".to_string()]);
 
-        let centrality = graph_analysis::betweenness_centrality(&adjacency);
+        let mut impact = graph_analysis::removal_impact(&adjacency);
 
-        // Expected output: Node removal results in correct largest connected component sizes
+        // Removing any single county from a triangle leaves the other two
+        // connected, so every county's largest remaining component is 2. The
+        // order among equal sizes depends on HashMap iteration, so sort by
+        // county before comparing rather than assuming a fixed order.
+        impact.sort_by(|a, b| a.0.cmp(&b.0));
         let expected = vec![
             ("A".to_string(), 2), // Removing A leaves B-C connected
             ("B".to_string(), 2), // Removing B leaves A-C connected
             ("C".to_string(), 2), // Removing C leaves A-B connected
         ];
 
-        assert_eq!(centrality, expected);
+        assert_eq!(impact, expected);
     }
 }
\ No newline at end of file